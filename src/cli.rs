@@ -0,0 +1,221 @@
+//! Hand-rolled, xflags-style argument parsing for the non-interactive CLI.
+//!
+//! With no arguments this falls back to the interactive REPL.
+
+use std::fmt;
+
+use crate::Action;
+
+/// The parsed command line, mirroring the `TerminalAction` variants that the
+/// interactive loop already supports.
+pub struct Cli {
+    pub command: Command,
+    /// `--input <path>`: an existing items JSON file to load before running
+    /// `command`, in the same shape `print_items_json` emits.
+    pub input: Option<String>,
+    /// `--output <path>`: where to atomically write the resulting items
+    /// JSON instead of printing it to stdout.
+    pub output: Option<String>,
+}
+
+pub enum Command {
+    /// No subcommand was given: fall back to the interactive REPL.
+    Interactive,
+    Add(AddArgs),
+    List,
+}
+
+/// Arguments for `generator add --filename <f> --action <ADD|DELETE|UPDATE> --link <url>`.
+pub struct AddArgs {
+    pub filename: String,
+    pub action: Action,
+    pub link: String,
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    UnknownSubcommand(String),
+    MissingValue(&'static str),
+    UnknownFlag(String),
+    InvalidAction(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownSubcommand(cmd) => {
+                write!(f, "WARNING: unknown subcommand '{}', expected one of: add, list", cmd)
+            }
+            CliError::MissingValue(flag) => {
+                write!(f, "WARNING: missing value for --{}, please try again.", flag)
+            }
+            CliError::UnknownFlag(flag) => {
+                write!(f, "WARNING: unknown flag '--{}', please try again.", flag)
+            }
+            CliError::InvalidAction(value) => {
+                write!(f, "WARNING: Invalid input, please try again. (invalid action: {})", value)
+            }
+        }
+    }
+}
+
+/// Parses `args` (typically `std::env::args().skip(1)`) into a [`Cli`].
+///
+/// An empty iterator yields [`Command::Interactive`] so the existing REPL
+/// keeps working unchanged when the binary is invoked with no arguments.
+/// `--input <path>` may appear anywhere and is pulled out before the
+/// subcommand itself is parsed.
+pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Cli, CliError> {
+    let (input, rest) = extract_input(args)?;
+    let (output, rest) = extract_output(rest.into_iter())?;
+    let mut rest = rest.into_iter();
+
+    let command = match rest.next() {
+        None => Command::Interactive,
+        Some(subcommand) => match subcommand.as_str() {
+            "add" => Command::Add(parse_add_args(rest)?),
+            "list" => Command::List,
+            other => return Err(CliError::UnknownSubcommand(other.to_string())),
+        },
+    };
+
+    Ok(Cli { command, input, output })
+}
+
+fn extract_input<I: Iterator<Item = String>>(args: I) -> Result<(Option<String>, Vec<String>), CliError> {
+    let mut input = None;
+    let mut rest = Vec::new();
+    let mut args = args;
+
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            input = Some(args.next().ok_or(CliError::MissingValue("input"))?);
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    Ok((input, rest))
+}
+
+fn extract_output<I: Iterator<Item = String>>(args: I) -> Result<(Option<String>, Vec<String>), CliError> {
+    let mut output = None;
+    let mut rest = Vec::new();
+    let mut args = args;
+
+    while let Some(arg) = args.next() {
+        if arg == "--output" {
+            output = Some(args.next().ok_or(CliError::MissingValue("output"))?);
+        } else {
+            rest.push(arg);
+        }
+    }
+
+    Ok((output, rest))
+}
+
+fn parse_add_args<I: Iterator<Item = String>>(args: I) -> Result<AddArgs, CliError> {
+    let mut filename: Option<String> = None;
+    let mut action: Option<Action> = None;
+    let mut link: Option<String> = None;
+
+    let mut args = args.peekable();
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--filename" => filename = Some(args.next().ok_or(CliError::MissingValue("filename"))?),
+            "--action" => {
+                let value = args.next().ok_or(CliError::MissingValue("action"))?;
+                action = Some(Action::try_from(value.as_str()).map_err(|_| CliError::InvalidAction(value))?);
+            }
+            "--link" => link = Some(args.next().ok_or(CliError::MissingValue("link"))?),
+            other => return Err(CliError::UnknownFlag(other.trim_start_matches("--").to_string())),
+        }
+    }
+
+    Ok(AddArgs {
+        filename: filename.ok_or(CliError::MissingValue("filename"))?,
+        action: action.ok_or(CliError::MissingValue("action"))?,
+        link: link.ok_or(CliError::MissingValue("link"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(parts: &[&str]) -> impl Iterator<Item = String> {
+        parts.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn no_args_falls_back_to_interactive() {
+        let cli = parse(args(&[])).unwrap();
+        assert!(matches!(cli.command, Command::Interactive));
+        assert_eq!(cli.input, None);
+        assert_eq!(cli.output, None);
+    }
+
+    #[test]
+    fn list_subcommand() {
+        let cli = parse(args(&["list"])).unwrap();
+        assert!(matches!(cli.command, Command::List));
+    }
+
+    #[test]
+    fn add_subcommand_with_all_flags() {
+        let cli = parse(args(&["add", "--filename", "f.jar", "--action", "add", "--link", "http://x"])).unwrap();
+        match cli.command {
+            Command::Add(add_args) => {
+                assert_eq!(add_args.filename, "f.jar");
+                assert_eq!(add_args.action, Action::Add);
+                assert_eq!(add_args.link, "http://x");
+            }
+            _ => panic!("expected Command::Add"),
+        }
+    }
+
+    #[test]
+    fn input_and_output_flags_are_extracted_regardless_of_position() {
+        let cli = parse(args(&["--input", "in.json", "list", "--output", "out.json"])).unwrap();
+        assert_eq!(cli.input, Some("in.json".to_string()));
+        assert_eq!(cli.output, Some("out.json".to_string()));
+        assert!(matches!(cli.command, Command::List));
+    }
+
+    fn parse_err(parts: &[&str]) -> CliError {
+        match parse(args(parts)) {
+            Err(err) => err,
+            Ok(_) => panic!("expected parse to fail for {:?}", parts),
+        }
+    }
+
+    #[test]
+    fn missing_value_for_input() {
+        assert!(matches!(parse_err(&["--input"]), CliError::MissingValue("input")));
+    }
+
+    #[test]
+    fn missing_value_for_output() {
+        assert!(matches!(parse_err(&["--output"]), CliError::MissingValue("output")));
+    }
+
+    #[test]
+    fn unknown_subcommand() {
+        assert!(matches!(parse_err(&["frobnicate"]), CliError::UnknownSubcommand(cmd) if cmd == "frobnicate"));
+    }
+
+    #[test]
+    fn add_missing_required_flag() {
+        assert!(matches!(parse_err(&["add", "--filename", "f.jar", "--link", "http://x"]), CliError::MissingValue("action")));
+    }
+
+    #[test]
+    fn add_unknown_flag() {
+        assert!(matches!(parse_err(&["add", "--bogus", "x"]), CliError::UnknownFlag(flag) if flag == "bogus"));
+    }
+
+    #[test]
+    fn add_invalid_action() {
+        assert!(matches!(parse_err(&["add", "--filename", "f.jar", "--action", "NUKE", "--link", "http://x"]), CliError::InvalidAction(value) if value == "NUKE"));
+    }
+}
@@ -0,0 +1,55 @@
+//! Crate-wide error type wrapping I/O, JSON, and line-editor failures.
+
+use std::fmt;
+use std::io;
+
+use crate::storage::SaveError;
+
+#[derive(Debug)]
+pub enum AppError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Readline(rustyline::error::ReadlineError),
+    Save(SaveError),
+    /// Ctrl+D or Ctrl+C at a prompt: the REPL should quit cleanly, the same
+    /// way `TerminalAction::Quit` does, rather than treat it as a failure.
+    Quit,
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Io(err) => write!(f, "I/O error: {}", err),
+            AppError::Json(err) => write!(f, "JSON error: {}", err),
+            AppError::Readline(err) => write!(f, "line editor error: {}", err),
+            AppError::Save(err) => write!(f, "save error: {}", err),
+            AppError::Quit => write!(f, "quit requested"),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<io::Error> for AppError {
+    fn from(err: io::Error) -> Self {
+        AppError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::Json(err)
+    }
+}
+
+impl From<rustyline::error::ReadlineError> for AppError {
+    fn from(err: rustyline::error::ReadlineError) -> Self {
+        AppError::Readline(err)
+    }
+}
+
+impl From<SaveError> for AppError {
+    fn from(err: SaveError) -> Self {
+        AppError::Save(err)
+    }
+}
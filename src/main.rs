@@ -1,18 +1,27 @@
 //! This small utility is to facilitate the creation/addition of items in the
 //! json list for the ModsUpgrader program.
 
-use std::io::Write;
+use serde::Deserialize;
 
+use crate::error::AppError;
+use crate::repl::Repl;
 use crate::terminal_actions::{
     AddItemCommand,
     DeleteItemCommand,
+    LoadItemCommand,
     ModifyItemCommand,
     QuitCommand,
+    SaveItemCommand,
     ShowItemsCommand,
     TerminalAction,
     TerminalCommand,
 };
 
+mod cli;
+mod error;
+mod repl;
+mod storage;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum Action {
     Add,
@@ -21,13 +30,20 @@ enum Action {
 }
 
 impl Action {
-    fn to_str(&self) -> &str {
+    fn to_str(&self) -> &'static str {
         match self {
             Action::Add => "ADD",
             Action::Delete => "DELETE",
             Action::Update => "UPDATE",
         }
     }
+
+    /// All variants as their wire strings, kept in sync with `to_str` since
+    /// it's used directly to build the completion candidates for the
+    /// action prompts.
+    fn candidates() -> [&'static str; 3] {
+        [Action::Add.to_str(), Action::Delete.to_str(), Action::Update.to_str()]
+    }
 }
 
 impl TryFrom<&str> for Action {
@@ -46,6 +62,16 @@ impl TryFrom<&str> for Action {
     }
 }
 
+impl<'de> Deserialize<'de> for Action {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Action::try_from(raw.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
 
 /// This struct models an item in the json.
 /// An `Item` has:
@@ -55,8 +81,9 @@ impl TryFrom<&str> for Action {
 /// `action`: an enum `Action` that variants model the action that the client will do with the `Item`.
 ///
 /// `download_link`: the direct link to the file.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 struct Item {
+    #[serde(rename = "mod_filename")]
     filename: String,
     action: Action,
     download_link: String,
@@ -72,26 +99,20 @@ impl Default for Item {
     }
 }
 
-/// This function displays a `message` to stdin using `print!` and returns the user input
-/// into an owned `String`.
-fn read_user_input(message: &str) -> String {
-    print!("{}", message);
-    std::io::stdout().flush().expect("ERROR: could not flush stdout!");
-
-    let mut buffer = String::new();
-    match std::io::stdin().read_line(&mut buffer) {
-        Ok(_) => buffer.trim().to_string(),
-        Err(_) => String::new(),
-    }
+/// This function displays a `message` as the prompt of `repl`'s line editor
+/// and returns the user input into an owned, trimmed `String`. `candidates`
+/// are offered as tab-completions for this prompt.
+fn read_user_input(repl: &mut Repl, message: &str, candidates: &[&str]) -> Result<String, AppError> {
+    repl.read_line(message, candidates)
 }
 
-fn read_user_item() -> Item {
+fn read_user_item(repl: &mut Repl) -> Result<Item, AppError> {
     println!("\n--- Adding a new item ---\n");
     let mut item = Item::default();
 
     // Get filename
     loop {
-        let user_input = read_user_input("Filename: ");
+        let user_input = read_user_input(repl, "Filename: ", &[])?;
         if user_input.is_empty() {
             println!("WARNING: filename is empty, please try again.");
             continue;
@@ -104,7 +125,7 @@ fn read_user_item() -> Item {
 
     // Get action
     loop {
-        let user_input = read_user_input("Action (ADD, DELETE, UPDATE): ").to_uppercase();
+        let user_input = read_user_input(repl, "Action (ADD, DELETE, UPDATE): ", &Action::candidates())?.to_uppercase();
         if Action::Add.to_str() == user_input {
             item.action = Action::Add;
             break;
@@ -124,7 +145,7 @@ fn read_user_item() -> Item {
 
     // Get download link
     loop {
-        let user_input = read_user_input("Direct download link: ");
+        let user_input = read_user_input(repl, "Direct download link: ", &[])?;
         if user_input.is_empty() {
             println!("WARNING: filename is empty, please try again.");
             continue;
@@ -133,10 +154,12 @@ fn read_user_item() -> Item {
         break;
     }
 
-    item
+    Ok(item)
 }
 
 mod terminal_actions {
+    use crate::error::AppError;
+    use crate::repl::Repl;
     use crate::{Action, INVALID_INPUT_MSG, Item, read_user_input, read_user_item};
 
     fn print_enumerated_items(items: &[Item]) {
@@ -148,14 +171,14 @@ mod terminal_actions {
     }
 
     // Read from stdin for user input.
-    fn read_item_idx(items: &[Item]) -> usize {
+    fn read_item_idx(repl: &mut Repl, items: &[Item]) -> Result<usize, AppError> {
         loop {
             print_enumerated_items(items);
-            let user_input = read_user_input("Enter the item number you wish to change: ");
+            let user_input = read_user_input(repl, "Enter the item number you wish to change: ", &[])?;
             match user_input.parse::<usize>() {
                 Ok(idx) => {
                     match idx.checked_sub(1) {
-                        Some(subbed_idx) if subbed_idx < items.len() => return subbed_idx,
+                        Some(subbed_idx) if subbed_idx < items.len() => return Ok(subbed_idx),
                         _ => {
                             println!("WARNING: index out of bounds, please try again.");
                             continue;
@@ -170,7 +193,7 @@ mod terminal_actions {
     }
 
     pub trait TerminalCommand {
-        fn execute(&mut self);
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError>;
     }
 
     pub enum TerminalAction {
@@ -179,33 +202,45 @@ mod terminal_actions {
         ModifyItem,
         DeleteItem,
         Show,
+        Load,
+        Save,
     }
 
-    fn print_items_json(items: &[Item]) {
-        let json_items: Vec<serde_json::Value> = items.iter().map(|item| {
+    pub(crate) fn items_to_json(items: &[Item]) -> serde_json::Value {
+        serde_json::Value::Array(items.iter().map(|item| {
             serde_json::json!({
                 "mod_filename": item.filename,
                 "action": item.action.to_str(),
                 "download_link": item.download_link,
-        })
-        }).collect();
+            })
+        }).collect())
+    }
 
-        let json_output = serde_json::to_string_pretty(&json_items).unwrap();
+    pub(crate) fn print_items_json(items: &[Item]) -> Result<(), AppError> {
+        let json_output = serde_json::to_string_pretty(&items_to_json(items))?;
         println!("\n\n{}", json_output);
+        Ok(())
     }
 
 
-    // Quit (quits and shows the final json)
+    // Quit (quits and shows the final json, or saves it if an output path was configured)
     pub struct QuitCommand<'a> {
         pub items: &'a Vec<Item>,
+        pub output: Option<&'a str>,
     }
 
     impl<'a> TerminalCommand for QuitCommand<'a> {
-        fn execute(&mut self) {
-            if !self.items.is_empty() {
-                print_items_json(self.items);
+        fn execute(&mut self, _repl: &mut Repl) -> Result<(), AppError> {
+            match self.output {
+                Some(path) if !self.items.is_empty() => {
+                    if let Err(err) = crate::storage::save_items(std::path::Path::new(path), self.items) {
+                        println!("WARNING: could not save to '{}': {}", path, err);
+                    }
+                }
+                None if !self.items.is_empty() => print_items_json(self.items)?,
+                _ => {}
             }
-            std::process::exit(0);
+            Ok(())
         }
     }
 
@@ -216,9 +251,10 @@ mod terminal_actions {
     }
 
     impl<'a> TerminalCommand for AddItemCommand<'a> {
-        fn execute(&mut self) {
-            let item = read_user_item();
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError> {
+            let item = read_user_item(repl)?;
             self.items.push(item);
+            Ok(())
         }
     }
 
@@ -229,37 +265,37 @@ mod terminal_actions {
     }
 
     impl<'a> ModifyItemCommand<'a> {
-        fn read_user() -> String {
+        fn read_user(repl: &mut Repl) -> Result<String, AppError> {
             println!("What do you want to modify?");
             loop {
-                let user_input = read_user_input("1: Filename\n2: Action\n3: Direct download link\n-> ");
+                let user_input = read_user_input(repl, "1: Filename\n2: Action\n3: Direct download link\n-> ", &["1", "2", "3"])?;
                 match user_input.as_str() {
-                    "1" | "2" | "3" => return user_input,
+                    "1" | "2" | "3" => return Ok(user_input),
                     _ => println!("{}", INVALID_INPUT_MSG),
                 }
             }
         }
 
-        fn modify_item(item: &mut Item) {
-            match Self::read_user().as_str() {
+        fn modify_item(repl: &mut Repl, item: &mut Item) -> Result<(), AppError> {
+            match Self::read_user(repl)?.as_str() {
                 "1" => { // filename
                     loop {
-                        let user_input = read_user_input("Filename new value: ");
+                        let user_input = read_user_input(repl, "Filename new value: ", &[])?;
                         if user_input.is_empty() {
                             println!("{}", INVALID_INPUT_MSG);
                             continue;
                         }
                         item.filename = user_input;
-                        return;
+                        return Ok(());
                     }
                 }
                 "2" => { // action
                     loop {
-                        let user_input = read_user_input("Action new value (ADD, DELETE, UPDATE) : ");
+                        let user_input = read_user_input(repl, "Action new value (ADD, DELETE, UPDATE) : ", &Action::candidates())?;
                         match Action::try_from(user_input.as_str()) {
                             Ok(action) => {
                                 item.action = action;
-                                return;
+                                return Ok(());
                             }
                             Err(_) => {
                                 println!("{}", INVALID_INPUT_MSG);
@@ -270,28 +306,28 @@ mod terminal_actions {
                 }
                 "3" => { // download link
                     loop {
-                        let user_input = read_user_input("Direct download link new value: ");
+                        let user_input = read_user_input(repl, "Direct download link new value: ", &[])?;
                         if user_input.is_empty() {
                             println!("{}", INVALID_INPUT_MSG);
                             continue;
                         }
                         item.download_link = user_input;
-                        return;
+                        return Ok(());
                     }
                 }
-                _ => {}
+                _ => Ok(()),
             }
         }
     }
 
     impl<'a> TerminalCommand for ModifyItemCommand<'a> {
-        fn execute(&mut self) {
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError> {
             if self.items.is_empty() {
                 println!("WARNING: no items, cannot modify.");
-                return;
+                return Ok(());
             }
-            let user_item_idx = read_item_idx(self.items);
-            Self::modify_item(&mut self.items[user_item_idx]);
+            let user_item_idx = read_item_idx(repl, self.items)?;
+            Self::modify_item(repl, &mut self.items[user_item_idx])
         }
     }
 
@@ -302,13 +338,57 @@ mod terminal_actions {
     }
 
     impl<'a> TerminalCommand for DeleteItemCommand<'a> {
-        fn execute(&mut self) {
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError> {
             if self.items.is_empty() {
                 println!("WARNING: no items, cannot delete.");
-                return;
+                return Ok(());
             }
-            let user_item_idx = read_item_idx(self.items);
+            let user_item_idx = read_item_idx(repl, self.items)?;
             self.items.remove(user_item_idx);
+            Ok(())
+        }
+    }
+
+    // Load
+    pub struct LoadItemCommand<'a> {
+        pub(crate) items: &'a mut Vec<Item>,
+    }
+
+    impl<'a> TerminalCommand for LoadItemCommand<'a> {
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError> {
+            let path = read_user_input(repl, "Path to items JSON file to load: ", &[])?;
+            if path.is_empty() {
+                println!("{}", INVALID_INPUT_MSG);
+                return Ok(());
+            }
+            match crate::storage::load_items(std::path::Path::new(&path)) {
+                Ok(mut loaded) => self.items.append(&mut loaded),
+                Err(err) => println!("WARNING: could not load '{}': {}", path, err),
+            }
+            Ok(())
+        }
+    }
+
+    // Save
+    pub struct SaveItemCommand<'a> {
+        pub(crate) items: &'a Vec<Item>,
+    }
+
+    impl<'a> TerminalCommand for SaveItemCommand<'a> {
+        fn execute(&mut self, repl: &mut Repl) -> Result<(), AppError> {
+            if self.items.is_empty() {
+                println!("WARNING: no items, cannot save.");
+                return Ok(());
+            }
+            let path = read_user_input(repl, "Path to save items JSON to: ", &[])?;
+            if path.is_empty() {
+                println!("{}", INVALID_INPUT_MSG);
+                return Ok(());
+            }
+            if let Err(err) = crate::storage::save_items(std::path::Path::new(&path), self.items) {
+                println!("WARNING: could not save to '{}': {}", path, err);
+            }
+            Ok(())
         }
     }
 
@@ -319,47 +399,128 @@ mod terminal_actions {
     }
 
     impl<'a> TerminalCommand for ShowItemsCommand<'a> {
-        fn execute(&mut self) {
+        fn execute(&mut self, _repl: &mut Repl) -> Result<(), AppError> {
             if self.items.is_empty() {
                 println!("WARNING: no items, cannot show.");
-                return;
+                return Ok(());
             }
-            print_items_json(self.items);
+            print_items_json(self.items)
         }
     }
 }
 
-fn get_terminal_action() -> TerminalAction {
+const TERMINAL_ACTION_CANDIDATES: [&str; 7] = ["q", "a", "m", "d", "s", "l", "w"];
+
+fn get_terminal_action(repl: &mut Repl) -> Result<TerminalAction, AppError> {
     loop {
-        let user_input = read_user_input("Enter command (q: Quit, a: Add, m: Modify, d: Delete, s: Show): ");
+        let user_input = read_user_input(
+            repl,
+            "Enter command (q: Quit, a: Add, m: Modify, d: Delete, s: Show, l: Load, w: Save): ",
+            &TERMINAL_ACTION_CANDIDATES,
+        )?;
         match user_input.to_lowercase().as_str() {
-            "q" => return TerminalAction::Quit,
-            "a" => return TerminalAction::AddItem,
-            "m" => return TerminalAction::ModifyItem,
-            "d" => return TerminalAction::DeleteItem,
-            "s" => return TerminalAction::Show,
+            "q" => return Ok(TerminalAction::Quit),
+            "a" => return Ok(TerminalAction::AddItem),
+            "m" => return Ok(TerminalAction::ModifyItem),
+            "d" => return Ok(TerminalAction::DeleteItem),
+            "s" => return Ok(TerminalAction::Show),
+            "l" => return Ok(TerminalAction::Load),
+            "w" => return Ok(TerminalAction::Save),
             _ => println!("{}", INVALID_INPUT_MSG)
         }
     }
 }
 
-fn execute_command(term_action: TerminalAction, items: &mut Vec<Item>) {
+fn execute_command(
+    term_action: TerminalAction,
+    items: &mut Vec<Item>,
+    output: Option<&str>,
+    repl: &mut Repl,
+) -> Result<(), AppError> {
     match term_action {
-        TerminalAction::Quit => QuitCommand { items }.execute(),
-        TerminalAction::AddItem => AddItemCommand { items }.execute(),
-        TerminalAction::ModifyItem => ModifyItemCommand { items }.execute(),
-        TerminalAction::DeleteItem => DeleteItemCommand { items }.execute(),
-        TerminalAction::Show => ShowItemsCommand { items }.execute(),
+        TerminalAction::Quit => QuitCommand { items, output }.execute(repl),
+        TerminalAction::AddItem => AddItemCommand { items }.execute(repl),
+        TerminalAction::ModifyItem => ModifyItemCommand { items }.execute(repl),
+        TerminalAction::DeleteItem => DeleteItemCommand { items }.execute(repl),
+        TerminalAction::Show => ShowItemsCommand { items }.execute(repl),
+        TerminalAction::Load => LoadItemCommand { items }.execute(repl),
+        TerminalAction::Save => SaveItemCommand { items }.execute(repl),
     }
 }
 
 
 const INVALID_INPUT_MSG: &str = "WARNING: Invalid input, please try again.";
 
-fn main() {
-    let mut items: Vec<Item> = Vec::new();
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = match cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    let mut items = load_input_items(cli.input.as_deref());
+
+    match cli.command {
+        cli::Command::Interactive => run_interactive(items, cli.output.as_deref())?,
+        cli::Command::Add(args) => {
+            items.push(Item {
+                filename: args.filename,
+                action: args.action,
+                download_link: args.link,
+            });
+            output_items(&items, cli.output.as_deref())?;
+        }
+        cli::Command::List => output_items(&items, cli.output.as_deref())?,
+    }
+
+    Ok(())
+}
+
+fn output_items(items: &[Item], output: Option<&str>) -> Result<(), AppError> {
+    match output {
+        Some(path) => Ok(storage::save_items(std::path::Path::new(path), items)?),
+        None => terminal_actions::print_items_json(items),
+    }
+}
+
+fn load_input_items(input: Option<&str>) -> Vec<Item> {
+    match input {
+        None => Vec::new(),
+        Some(path) => match storage::load_items(std::path::Path::new(path)) {
+            Ok(items) => items,
+            Err(err) => {
+                eprintln!("WARNING: could not load '{}': {}", path, err);
+                Vec::new()
+            }
+        },
+    }
+}
+
+fn run_interactive(mut items: Vec<Item>, output: Option<&str>) -> Result<(), AppError> {
+    let mut repl = Repl::new()?;
     loop {
-        let action = get_terminal_action();
-        execute_command(action, &mut items);
+        let action = match get_terminal_action(&mut repl) {
+            Ok(action) => action,
+            Err(AppError::Quit) => TerminalAction::Quit,
+            Err(err) => return Err(err),
+        };
+        let is_quit = matches!(action, TerminalAction::Quit);
+
+        match execute_command(action, &mut items, output, &mut repl) {
+            Ok(()) => {}
+            // Ctrl+D/Ctrl+C mid-prompt: quit and save just like typing `q` would.
+            Err(AppError::Quit) => {
+                QuitCommand { items: &items, output }.execute(&mut repl)?;
+                break;
+            }
+            Err(err) => return Err(err),
+        }
+
+        if is_quit {
+            break;
+        }
     }
+    Ok(())
 }
@@ -0,0 +1,105 @@
+//! Line editing, history, and tab-completion for the interactive REPL.
+//!
+//! One [`Repl`] is threaded through every prompt for the life of a session.
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::error::AppError;
+
+const HISTORY_FILE_NAME: &str = ".modpatcher_json_generator_history";
+
+#[derive(Default)]
+struct CommandHelper {
+    candidates: RefCell<Vec<String>>,
+}
+
+impl Completer for CommandHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let prefix = &line[..pos];
+        let matches = self
+            .candidates
+            .borrow()
+            .iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&prefix.to_lowercase()))
+            .map(|candidate| Pair {
+                display: candidate.clone(),
+                replacement: candidate.clone(),
+            })
+            .collect();
+        Ok((0, matches))
+    }
+}
+
+impl Hinter for CommandHelper {
+    type Hint = String;
+}
+
+impl Highlighter for CommandHelper {}
+impl Validator for CommandHelper {}
+impl Helper for CommandHelper {}
+
+pub struct Repl {
+    editor: Editor<CommandHelper, DefaultHistory>,
+    history_path: PathBuf,
+}
+
+impl Repl {
+    pub fn new() -> Result<Self, AppError> {
+        let mut editor: Editor<CommandHelper, DefaultHistory> = Editor::new()?;
+        editor.set_helper(Some(CommandHelper::default()));
+
+        let history_path = history_path();
+        // A missing or unreadable history file just means an empty history.
+        let _ = editor.load_history(&history_path);
+
+        Ok(Self { editor, history_path })
+    }
+
+    /// Reads one line, offering tab-completion over `candidates` and
+    /// recalling any previously entered line (from any prompt) via the up
+    /// arrow. Ctrl+D/Ctrl+C yield `AppError::Quit` rather than a raw
+    /// `ReadlineError`, so callers can send the REPL through the same
+    /// clean-quit path as typing `q`.
+    pub fn read_line(&mut self, prompt: &str, candidates: &[&str]) -> Result<String, AppError> {
+        if let Some(helper) = self.editor.helper_mut() {
+            *helper.candidates.borrow_mut() = candidates.iter().map(|c| c.to_string()).collect();
+        }
+
+        let line = match self.editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Eof) | Err(ReadlineError::Interrupted) => return Err(AppError::Quit),
+            Err(err) => return Err(err.into()),
+        };
+        let trimmed = line.trim().to_string();
+
+        if !trimmed.is_empty() {
+            let _ = self.editor.add_history_entry(trimmed.as_str());
+            let _ = self.editor.save_history(&self.history_path);
+        }
+
+        Ok(trimmed)
+    }
+}
+
+fn history_path() -> PathBuf {
+    match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(HISTORY_FILE_NAME),
+        None => PathBuf::from(HISTORY_FILE_NAME),
+    }
+}
@@ -0,0 +1,231 @@
+//! Loading and saving an items JSON file.
+//!
+//! Shape matches `print_items_json`: an array of `mod_filename`/`action`/`download_link` objects.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use crate::terminal_actions::items_to_json;
+use crate::Item;
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Parse(serde_json::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "could not read file: {}", err),
+            LoadError::Parse(err) => write!(f, "not a valid items JSON array: {}", err),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Serialize(serde_json::Error),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "could not write file: {}", err),
+            SaveError::Serialize(err) => write!(f, "could not serialize items: {}", err),
+        }
+    }
+}
+
+/// Atomically writes `items` to `path`.
+///
+/// The JSON is first written to a `.tmp` sibling file, flushed and synced to
+/// disk, then renamed over `path` so a crash mid-write never leaves a
+/// half-written list. If `path` already holds a file, its previous contents
+/// are best-effort copied to a `.bak` sibling first.
+pub fn save_items(path: &Path, items: &[Item]) -> Result<(), SaveError> {
+    let json_output = serde_json::to_string_pretty(&items_to_json(items)).map_err(SaveError::Serialize)?;
+
+    if path.exists() {
+        let backup_path = append_extension(path, "bak");
+        if let Err(err) = fs::copy(path, &backup_path) {
+            eprintln!("WARNING: could not back up '{}': {}", path.display(), err);
+        }
+    }
+
+    let tmp_path = append_extension(path, "tmp");
+    let mut tmp_file = fs::File::create(&tmp_path).map_err(SaveError::Io)?;
+    tmp_file.write_all(json_output.as_bytes()).map_err(SaveError::Io)?;
+    tmp_file.sync_all().map_err(SaveError::Io)?;
+
+    fs::rename(&tmp_path, path).map_err(SaveError::Io)
+}
+
+fn append_extension(path: &Path, extension: &str) -> std::path::PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    path.with_file_name(file_name)
+}
+
+/// Reads the items JSON array at `path`.
+///
+/// Entries that don't match the `Item` shape are skipped with a warning
+/// printed to stderr (including their offending index) rather than
+/// aborting the whole load.
+pub fn load_items(path: &Path) -> Result<Vec<Item>, LoadError> {
+    let contents = fs::read_to_string(path).map_err(LoadError::Io)?;
+    let raw: Vec<serde_json::Value> = serde_json::from_str(&contents).map_err(LoadError::Parse)?;
+
+    let mut items = Vec::with_capacity(raw.len());
+    for (idx, value) in raw.into_iter().enumerate() {
+        match serde_json::from_value::<Item>(value) {
+            Ok(item) => items.push(item),
+            Err(err) => eprintln!(
+                "WARNING: skipping item {} in '{}': {}",
+                idx + 1,
+                path.display(),
+                err
+            ),
+        }
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod load_tests {
+    use super::*;
+    use crate::Action;
+
+    fn write_tmp(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("mod_patcher_json_generator_test_{}_{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn loads_well_formed_items() {
+        let path = write_tmp(
+            "load_ok.json",
+            r#"[{"mod_filename":"a.jar","action":"ADD","download_link":"http://x"}]"#,
+        );
+        let items = load_items(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].filename, "a.jar");
+        assert_eq!(items[0].action, Action::Add);
+        assert_eq!(items[0].download_link, "http://x");
+    }
+
+    #[test]
+    fn skips_malformed_entries_but_keeps_valid_ones() {
+        let path = write_tmp(
+            "load_skip.json",
+            r#"[{"mod_filename":"a.jar","action":"BOGUS","download_link":"http://x"},{"mod_filename":"b.jar","action":"DELETE","download_link":"http://y"}]"#,
+        );
+        let items = load_items(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].filename, "b.jar");
+    }
+
+    #[test]
+    fn missing_file_is_an_io_error() {
+        let path = std::env::temp_dir().join("mod_patcher_json_generator_test_does_not_exist.json");
+        assert!(matches!(load_items(&path), Err(LoadError::Io(_))));
+    }
+
+    #[test]
+    fn not_an_array_is_a_parse_error() {
+        let path = write_tmp("load_bad_shape.json", "{}");
+        let result = load_items(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(LoadError::Parse(_))));
+    }
+}
+
+#[cfg(test)]
+mod save_tests {
+    use super::*;
+    use crate::Action;
+
+    fn tmp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("mod_patcher_json_generator_test_{}_{}", std::process::id(), name))
+    }
+
+    fn item(filename: &str) -> Item {
+        Item {
+            filename: filename.to_string(),
+            action: Action::Add,
+            download_link: "http://x".to_string(),
+        }
+    }
+
+    #[test]
+    fn writes_items_and_round_trips_through_load() {
+        let path = tmp_path("save_roundtrip.json");
+        let _ = fs::remove_file(&path);
+
+        save_items(&path, &[item("a.jar")]).unwrap();
+        let loaded = load_items(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].filename, "a.jar");
+    }
+
+    #[test]
+    fn leaves_no_tmp_file_behind_after_a_successful_save() {
+        let path = tmp_path("save_no_tmp_leftover.json");
+        let tmp = append_extension(&path, "tmp");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&tmp);
+
+        save_items(&path, &[item("a.jar")]).unwrap();
+        let tmp_exists = tmp.exists();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!tmp_exists);
+    }
+
+    #[test]
+    fn backs_up_existing_file_before_overwriting() {
+        let path = tmp_path("save_backup.json");
+        let backup = append_extension(&path, "bak");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&backup);
+
+        save_items(&path, &[item("a.jar")]).unwrap();
+        save_items(&path, &[item("b.jar")]).unwrap();
+
+        let backed_up = load_items(&backup).unwrap();
+        let current = load_items(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        fs::remove_file(&backup).unwrap();
+
+        assert_eq!(backed_up[0].filename, "a.jar");
+        assert_eq!(current[0].filename, "b.jar");
+    }
+
+    #[test]
+    fn does_not_back_up_when_no_file_previously_existed() {
+        let path = tmp_path("save_no_prior_backup.json");
+        let backup = append_extension(&path, "bak");
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&backup);
+
+        save_items(&path, &[item("a.jar")]).unwrap();
+        let backup_exists = backup.exists();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!backup_exists);
+    }
+}